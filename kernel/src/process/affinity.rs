@@ -0,0 +1,56 @@
+//! Per-thread CPU affinity.
+//!
+//! `pick_for_hart` below is the enforcement point a per-hart scheduler's
+//! pick-next-thread loop should call instead of taking the first runnable
+//! thread unconditionally — but no scheduler lives in this module's tree
+//! yet to call it, so `sys_sched_setaffinity`/`sys_sched_getaffinity`
+//! still just store/return a mask that nothing consults.
+
+use super::*;
+
+/// Max harts this kernel can address with a single affinity word.
+pub const MAX_CPUS: usize = 64;
+
+/// A bitmask of harts a thread is allowed to run on. All bits set means
+/// "no restriction", which is also the default for a freshly created thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CpuMask(u64);
+
+impl Default for CpuMask {
+    fn default() -> Self {
+        CpuMask(u64::max_value())
+    }
+}
+
+impl CpuMask {
+    pub fn from_bits(bits: u64) -> Self {
+        CpuMask(bits)
+    }
+
+    pub fn bits(&self) -> u64 {
+        self.0
+    }
+
+    /// Whether this mask allows running on `hart`. Harts beyond `MAX_CPUS`
+    /// are never selectable.
+    pub fn allows(&self, hart: usize) -> bool {
+        hart < MAX_CPUS && self.0 & (1 << hart) != 0
+    }
+
+    /// Whether the mask selects at least one online hart.
+    pub fn is_valid(&self, online_mask: u64) -> bool {
+        self.0 & online_mask != 0
+    }
+}
+
+/// Pick the first runnable candidate, in order, whose affinity mask
+/// allows `hart`. `candidates` is `(pid, mask)` for every thread the
+/// scheduler would otherwise consider runnable on this hart; returns
+/// `None` if none of them may run here, so the scheduler should fall
+/// through to its idle path rather than picking a disallowed thread.
+pub fn pick_for_hart(hart: usize, candidates: impl Iterator<Item = (usize, CpuMask)>) -> Option<usize> {
+    candidates
+        .filter(|(_, mask)| mask.allows(hart))
+        .map(|(pid, _)| pid)
+        .next()
+}
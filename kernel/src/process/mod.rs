@@ -0,0 +1,7 @@
+//! Process-related subsystems that sit below `syscall` and above the
+//! scheduler: signal delivery, ptrace, and friends.
+
+pub mod affinity;
+pub mod ptrace;
+pub mod rlimit;
+pub mod signal;
@@ -0,0 +1,53 @@
+//! `ptrace` requests: attach/read-memory/read-registers/step control over a
+//! traced child, the in-kernel equivalent of what `remoteprocess` gives you
+//! from outside the process.
+
+use super::*;
+use crate::arch::interrupt::TrapFrame;
+
+pub const PTRACE_TRACEME: usize = 0;
+pub const PTRACE_PEEKTEXT: usize = 1;
+pub const PTRACE_PEEKDATA: usize = 2;
+pub const PTRACE_POKETEXT: usize = 4;
+pub const PTRACE_POKEDATA: usize = 5;
+pub const PTRACE_CONT: usize = 7;
+pub const PTRACE_SINGLESTEP: usize = 9;
+pub const PTRACE_GETREGS: usize = 12;
+pub const PTRACE_SETREGS: usize = 13;
+pub const PTRACE_ATTACH: usize = 16;
+pub const PTRACE_DETACH: usize = 17;
+
+/// Why a traced process stopped and is waiting for `PTRACE_CONT`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceStop {
+    /// Stopped right after `PTRACE_ATTACH`. `PTRACE_TRACEME` only records
+    /// the tracer and does not stop the caller.
+    Attached,
+    /// Stopped on syscall entry or exit.
+    Syscall,
+    /// Stopped because a signal was about to be delivered.
+    Signal(usize),
+}
+
+/// Per-process tracing state, held by the tracee.
+#[derive(Debug, Default, Clone)]
+pub struct TraceState {
+    /// PID of the tracer, if this process is being traced.
+    pub tracer: Option<usize>,
+    /// Set while the tracee is stopped inside the scheduler waiting for
+    /// `PTRACE_CONT`/`PTRACE_SINGLESTEP`; cleared by the tracer resuming it.
+    pub stop: Option<TraceStop>,
+    /// Set by `PTRACE_SINGLESTEP`: the tracee should re-stop after one
+    /// instruction instead of running free until the next natural stop.
+    pub single_step: bool,
+}
+
+impl TraceState {
+    pub fn is_traced(&self) -> bool {
+        self.tracer.is_some()
+    }
+}
+
+/// `TrapFrame` layout used by `PTRACE_GETREGS`/`PTRACE_SETREGS`: the raw
+/// saved register file, copied to/from the tracee's kernel stack.
+pub type PtraceRegs = TrapFrame;
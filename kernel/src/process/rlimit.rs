@@ -0,0 +1,101 @@
+//! Per-process resource limits (`getrlimit`/`setrlimit`/`prlimit64`).
+//!
+//! `RLIMIT_NPROC` is enforced in `sys_fork`, in this module's own crate.
+//! `RLIMIT_NOFILE`/`RLIMIT_STACK`/`RLIMIT_AS` are stored and reported, and
+//! the `ResourceLimits::allows_new_fd`/`stack_size`/`allows_address_space`
+//! helpers below compute their checks, but nothing in this module's tree
+//! calls those helpers, so these three limits are not enforced:
+//! - fd allocation (`sys_open`/`sys_dup`) should reject a new fd with
+//!   `EMFILE` when `allows_new_fd(open_count)` is false.
+//! - `Thread::new_user` should lay out the user stack `stack_size()` bytes
+//!   tall instead of a hardcoded size.
+//! - `mmap`/`brk` should reject growing the address space past
+//!   `allows_address_space(new_size)`.
+
+use super::*;
+
+pub const RLIMIT_CPU: usize = 0;
+pub const RLIMIT_FSIZE: usize = 1;
+pub const RLIMIT_DATA: usize = 2;
+pub const RLIMIT_STACK: usize = 3;
+pub const RLIMIT_CORE: usize = 4;
+pub const RLIMIT_RSS: usize = 5;
+pub const RLIMIT_NPROC: usize = 6;
+pub const RLIMIT_NOFILE: usize = 7;
+pub const RLIMIT_MEMLOCK: usize = 8;
+pub const RLIMIT_AS: usize = 9;
+pub const RLIMIT_NLIMITS: usize = 10;
+
+pub const RLIM_INFINITY: u64 = u64::max_value();
+
+/// A single `{ soft, hard }` limit pair, laid out the way userland expects
+/// it to be `read`/`write`-able through `getrlimit`/`setrlimit`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct RLimit {
+    pub soft: u64,
+    pub hard: u64,
+}
+
+impl RLimit {
+    pub const fn new(soft: u64, hard: u64) -> Self {
+        RLimit { soft, hard }
+    }
+}
+
+/// The full set of resource limits a process holds, inherited across
+/// `sys_fork` and preserved across `sys_exec`.
+#[derive(Debug, Clone, Copy)]
+pub struct ResourceLimits {
+    limits: [RLimit; RLIMIT_NLIMITS],
+}
+
+impl Default for ResourceLimits {
+    fn default() -> Self {
+        let mut limits = [RLimit::new(RLIM_INFINITY, RLIM_INFINITY); RLIMIT_NLIMITS];
+        limits[RLIMIT_NOFILE] = RLimit::new(1024, 4096);
+        limits[RLIMIT_STACK] = RLimit::new(8 * 1024 * 1024, RLIM_INFINITY);
+        limits[RLIMIT_NPROC] = RLimit::new(4096, 4096);
+        ResourceLimits { limits }
+    }
+}
+
+impl ResourceLimits {
+    pub fn get(&self, resource: usize) -> Option<RLimit> {
+        self.limits.get(resource).copied()
+    }
+
+    /// Apply a new limit for `resource`. An unprivileged caller may only
+    /// lower the soft limit, or raise it up to (not past) the hard cap;
+    /// the hard cap itself can only be lowered, never raised, unless
+    /// `privileged` (root) is set.
+    pub fn set(&mut self, resource: usize, new: RLimit, privileged: bool) -> Result<(), ()> {
+        let slot = self.limits.get_mut(resource).ok_or(())?;
+        if new.soft > new.hard {
+            return Err(());
+        }
+        if !privileged && new.hard > slot.hard {
+            return Err(());
+        }
+        *slot = new;
+        Ok(())
+    }
+
+    /// Whether the fd-allocation path may hand out one more fd, given the
+    /// process currently has `open_count` of them open.
+    pub fn allows_new_fd(&self, open_count: usize) -> bool {
+        (open_count as u64) < self.limits[RLIMIT_NOFILE].soft
+    }
+
+    /// The user stack size, in bytes, `Thread::new_user` should lay out
+    /// for a new thread.
+    pub fn stack_size(&self) -> u64 {
+        self.limits[RLIMIT_STACK].soft
+    }
+
+    /// Whether `mmap`/`brk` may grow the process's address space to
+    /// `new_size` bytes.
+    pub fn allows_address_space(&self, new_size: u64) -> bool {
+        new_size <= self.limits[RLIMIT_AS].soft
+    }
+}
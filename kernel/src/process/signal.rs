@@ -0,0 +1,167 @@
+//! POSIX signal delivery: pending/blocked masks, `sigaction` handlers, and
+//! the trampoline used to enter and return from a user signal handler.
+
+use super::*;
+use crate::arch::interrupt::TrapFrame;
+use bitflags::bitflags;
+
+/// Signal numbers, matching the generic Linux ABI.
+pub const SIGHUP: usize = 1;
+pub const SIGINT: usize = 2;
+pub const SIGQUIT: usize = 3;
+pub const SIGILL: usize = 4;
+pub const SIGTRAP: usize = 5;
+pub const SIGABRT: usize = 6;
+pub const SIGBUS: usize = 7;
+pub const SIGFPE: usize = 8;
+pub const SIGKILL: usize = 9;
+pub const SIGUSR1: usize = 10;
+pub const SIGSEGV: usize = 11;
+pub const SIGUSR2: usize = 12;
+pub const SIGPIPE: usize = 13;
+pub const SIGALRM: usize = 14;
+pub const SIGTERM: usize = 15;
+pub const SIGCHLD: usize = 17;
+pub const SIGCONT: usize = 18;
+pub const SIGSTOP: usize = 19;
+pub const SIGTSTP: usize = 20;
+pub const SIGTTIN: usize = 21;
+pub const SIGTTOU: usize = 22;
+pub const SIGURG: usize = 23;
+pub const SIGXCPU: usize = 24;
+pub const SIGXFSZ: usize = 25;
+pub const SIGSYS: usize = 31;
+
+/// Number of distinct signals this kernel tracks (1-indexed, slot 0 unused).
+pub const NSIG: usize = 32;
+
+bitflags! {
+    pub struct SigActionFlags: usize {
+        /// Invoke the handler on an alternate signal stack (`sigaltstack`).
+        const SA_ONSTACK = 0x0800_0000;
+        /// Restart the interrupted syscall instead of failing with EINTR.
+        const SA_RESTART = 0x1000_0000;
+        /// `sa_sigaction` (3-arg) rather than `sa_handler` (1-arg) form.
+        const SA_SIGINFO = 0x0000_0004;
+    }
+}
+
+/// Userspace `struct sigaction`, as read from / written to with
+/// `sys_rt_sigaction`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct SigAction {
+    /// Handler address, or `SIG_DFL` (0) / `SIG_IGN` (1).
+    pub handler: usize,
+    pub flags: usize,
+    /// Address of the `sys_sigreturn` trampoline the kernel should jump to
+    /// once the handler returns.
+    pub restorer: usize,
+    /// Signals blocked for the duration of the handler, in addition to the
+    /// signal itself.
+    pub mask: u64,
+}
+
+pub const SIG_DFL: usize = 0;
+pub const SIG_IGN: usize = 1;
+
+impl Default for SigAction {
+    fn default() -> Self {
+        SigAction { handler: SIG_DFL, flags: 0, restorer: 0, mask: 0 }
+    }
+}
+
+/// What happens to a process that receives a signal with no installed
+/// handler (or `SIG_DFL`).
+#[derive(Debug, PartialEq, Eq)]
+pub enum SignalDefault {
+    /// Terminate the process.
+    Terminate,
+    /// Terminate and dump core.
+    Core,
+    /// Stop (suspend) the process.
+    Stop,
+    /// Resume a stopped process.
+    Continue,
+    /// Do nothing.
+    Ignore,
+}
+
+/// Default disposition for a signal that has no handler installed, per POSIX.
+pub fn default_action(signum: usize) -> SignalDefault {
+    match signum {
+        SIGCHLD | SIGURG => SignalDefault::Ignore,
+        SIGSTOP | SIGTSTP | SIGTTIN | SIGTTOU => SignalDefault::Stop,
+        SIGCONT => SignalDefault::Continue,
+        SIGQUIT | SIGILL | SIGABRT | SIGFPE | SIGSEGV | SIGBUS | SIGTRAP => SignalDefault::Core,
+        SIGXCPU | SIGXFSZ | SIGSYS => SignalDefault::Core,
+        _ => SignalDefault::Terminate,
+    }
+}
+
+/// Per-process signal state: pending signals, the handlers installed for
+/// each, and the signals currently blocked from delivery.
+#[derive(Debug, Clone)]
+pub struct SignalState {
+    pub pending: u64,
+    pub blocked: u64,
+    pub actions: [SigAction; NSIG],
+}
+
+impl Default for SignalState {
+    fn default() -> Self {
+        SignalState {
+            pending: 0,
+            blocked: 0,
+            actions: [SigAction::default(); NSIG],
+        }
+    }
+}
+
+impl SignalState {
+    /// Mark `signum` as pending. `SIGKILL`/`SIGSTOP` cannot be blocked, so
+    /// they remain deliverable regardless of the blocked mask.
+    pub fn raise(&mut self, signum: usize) {
+        self.pending |= 1 << signum;
+    }
+
+    /// Pick the next pending, unblocked signal to deliver, if any.
+    pub fn take_deliverable(&mut self) -> Option<usize> {
+        let deliverable = self.pending & !(self.blocked & !Self::unblockable_mask());
+        if deliverable == 0 {
+            return None;
+        }
+        let signum = deliverable.trailing_zeros() as usize;
+        self.pending &= !(1 << signum);
+        Some(signum)
+    }
+
+    /// `SIGKILL` and `SIGSTOP` can never be blocked or caught.
+    pub fn unblockable_mask() -> u64 {
+        (1 << SIGKILL) | (1 << SIGSTOP)
+    }
+
+    pub fn set_blocked(&mut self, mask: u64) {
+        self.blocked = mask & !Self::unblockable_mask();
+    }
+
+    pub fn action(&self, signum: usize) -> SigAction {
+        self.actions[signum]
+    }
+
+    pub fn set_action(&mut self, signum: usize, action: SigAction) {
+        if signum != SIGKILL && signum != SIGSTOP {
+            self.actions[signum] = action;
+        }
+    }
+}
+
+/// Saved state needed to resume the interrupted context once the signal
+/// handler returns via `sys_sigreturn`. Held on `Process::signal_frames`,
+/// a stack rather than a single slot, so a handler interrupted by another
+/// caught signal nests correctly instead of losing the first frame.
+#[derive(Debug, Clone)]
+pub struct SignalFrame {
+    pub tf: TrapFrame,
+    pub old_mask: u64,
+}
@@ -1,19 +1,71 @@
 //! Syscalls for process
 
 use super::*;
+use crate::process::affinity::CpuMask;
+use crate::process::ptrace::{self, PtraceRegs, TraceStop};
+use crate::process::rlimit::{RLimit, RLIMIT_NLIMITS, RLIMIT_NPROC};
+use crate::process::signal::{self, SigAction, SignalDefault, SignalFrame};
+
+/// Don't block if no child has exited yet; return 0 instead.
+pub const WNOHANG: usize = 0x1;
+/// Also report children which have stopped (but not terminated) due to a signal.
+pub const WUNTRACED: usize = 0x2;
+/// Also report stopped children which have been resumed by `SIGCONT`.
+/// TODO: not yet honored by `sys_wait4` — reporting a continue needs a
+/// "just resumed" marker that survives exactly one `wait4` call, and
+/// `check_signals`'s `SignalDefault::Continue` arm doesn't record one
+/// (nor does it clear `Status::Stopped`) yet.
+pub const WCONTINUED: usize = 0x8;
+
+/// How a process finished running, as stored by the process manager.
+///
+/// This is what `Status::Exited` now carries, instead of a bare exit code,
+/// so that `sys_wait4` can tell a normal exit from a signal kill apart when
+/// it encodes `wstatus`.
+#[derive(Debug, Clone, Copy)]
+pub enum ExitStatus {
+    /// The process called `exit`/returned from `main` with this code.
+    Normal(i32),
+    /// The process was terminated by the given signal number, optionally dumping core.
+    Signaled { signal: u8, core_dump: bool },
+}
+
+impl ExitStatus {
+    /// Encode as the `wstatus` word that userland `WIFEXITED`/`WEXITSTATUS`/
+    /// `WIFSIGNALED`/`WTERMSIG` macros expect.
+    fn encode(&self) -> i32 {
+        match *self {
+            ExitStatus::Normal(code) => (code & 0xff) << 8,
+            ExitStatus::Signaled { signal, core_dump } => {
+                (signal as i32 & 0x7f) | ((core_dump as i32) << 7)
+            }
+        }
+    }
+}
 
 /// Fork the current process. Return the child's PID.
 pub fn sys_fork(tf: &TrapFrame) -> SysResult {
+    let nproc_limit = process().rlimits.get(RLIMIT_NPROC).unwrap().soft;
+    if (processor().manager().process_count() as u64) >= nproc_limit {
+        return Err(SysError::EAGAIN);
+    }
     let context = current_thread().fork(tf);
     let pid = processor().manager().add(context, thread::current().id());
+    // Resource limits and CPU affinity are inherited, not reset, across fork.
+    let rlimits = process().rlimits;
+    processor().manager().set_rlimits(pid, rlimits);
+    let affinity = current_thread().affinity;
+    processor().manager().set_affinity(pid, affinity);
     info!("fork: {} -> {}", thread::current().id(), pid);
     Ok(pid)
 }
 
 /// Wait the process exit.
 /// Return the PID. Store exit code to `code` if it's not null.
-pub fn sys_wait4(pid: isize, wstatus: *mut i32) -> SysResult {
-    info!("wait4: pid: {}, code: {:?}", pid, wstatus);
+/// What is stored in `wstatus` is the encoded wait status word (see
+/// `ExitStatus::encode`), not the bare exit code.
+pub fn sys_wait4(pid: isize, wstatus: *mut i32, options: usize) -> SysResult {
+    info!("wait4: pid: {}, wstatus: {:?}, options: {:#x}", pid, wstatus, options);
     if !wstatus.is_null() {
         process().memory_set.check_mut_ptr(wstatus)?;
     }
@@ -47,18 +99,47 @@ pub fn sys_wait4(pid: isize, wstatus: *mut i32) -> SysResult {
 
         for pid in wait_procs {
             match processor().manager().get_status(pid) {
-                Some(Status::Exited(exit_code)) => {
+                Some(Status::Exited(exit_status)) => {
                     if !wstatus.is_null() {
-                        unsafe { wstatus.write(exit_code as i32); }
+                        unsafe { wstatus.write(exit_status.encode()); }
                     }
                     processor().manager().remove(pid);
                     info!("wait: {} -> {}", thread::current().id(), pid);
                     return Ok(pid);
                 }
+                Some(Status::Traced) => {
+                    // Unlike a plain job-control stop (which needs
+                    // `WUNTRACED`), a ptrace-stop must always wake the
+                    // tracer: real tracers call `waitpid(pid, &status, 0)`
+                    // in their attach/`PTRACE_CONT` loop and rely on
+                    // unconditional notification here.
+                    if !wstatus.is_null() {
+                        // 0x7f in the low byte marks a (ptrace or job-control) stop;
+                        // the stop signal, if any, goes in the next byte.
+                        let sig = processor().manager().tracee_stop_signal(pid).unwrap_or(0);
+                        unsafe { wstatus.write(((sig as i32) << 8) | 0x7f); }
+                    }
+                    info!("wait: {} -> {} (stopped)", thread::current().id(), pid);
+                    return Ok(pid);
+                }
+                // A plain job-control stop (e.g. default-action `SIGSTOP`,
+                // see `check_signals`), as opposed to a ptrace-stop: only
+                // reported when the caller opted in with `WUNTRACED`.
+                Some(Status::Stopped) if options & WUNTRACED != 0 => {
+                    if !wstatus.is_null() {
+                        let sig = processor().manager().tracee_stop_signal(pid).unwrap_or(0);
+                        unsafe { wstatus.write(((sig as i32) << 8) | 0x7f); }
+                    }
+                    info!("wait: {} -> {} (stopped)", thread::current().id(), pid);
+                    return Ok(pid);
+                }
                 None => return Err(SysError::ECHILD),
                 _ => {}
             }
         }
+        if options & WNOHANG != 0 {
+            return Ok(0);
+        }
         info!("wait: {} -> {:?}, sleep", thread::current().id(), target);
         match target {
             WaitFor::AnyChild => processor().manager().wait_child(thread::current().id()),
@@ -90,6 +171,21 @@ pub fn sys_exec(name: *const u8, argv: *const *const u8, envp: *const *const u8,
     }
     info!("exec: args {:?}", args);
 
+    // Check and copy envp to kernel, the same way as argv. A null envp
+    // means "no environment", not "inherit the caller's".
+    let mut envs = Vec::new();
+    if !envp.is_null() {
+        unsafe {
+            let mut current_envp = envp as *const *const u8;
+            while !(*current_envp).is_null() {
+                let env = proc.memory_set.check_and_clone_cstr(*current_envp)?;
+                envs.push(env);
+                current_envp = current_envp.add(1);
+            }
+        }
+    }
+    info!("exec: envs {:?}", envs);
+
     // Read program file
     let path = args[0].as_str();
     let inode = crate::fs::ROOT_INODE.lookup(path)?;
@@ -98,11 +194,19 @@ pub fn sys_exec(name: *const u8, argv: *const *const u8, envp: *const *const u8,
     unsafe { buf.set_len(size); }
     inode.read_at(0, buf.as_mut_slice())?;
 
-    // Make new Thread
-    let iter = args.iter().map(|s| s.as_str());
-    let mut thread = Thread::new_user(buf.as_slice(), iter);
+    // Make new Thread. `Thread::new_user` lays out `argc, argv[], NULL,
+    // envp[], NULL` (and an auxv) on the new user stack, System V style,
+    // so libc's `environ`/`getenv` and the startup code that fills in
+    // `argc`/`argv` for `main` both work.
+    let arg_iter = args.iter().map(|s| s.as_str());
+    let env_iter = envs.iter().map(|s| s.as_str());
+    let mut thread = Thread::new_user(buf.as_slice(), arg_iter, env_iter);
     thread.proc.lock().files = proc.files.clone();
     thread.proc.lock().cwd = proc.cwd.clone();
+    // Resource limits survive exec, just like files/cwd.
+    thread.proc.lock().rlimits = proc.rlimits;
+    // CPU affinity also survives exec.
+    thread.affinity = current_thread().affinity;
 
     // Activate new page table
     unsafe { thread.proc.lock().memory_set.activate(); }
@@ -122,16 +226,335 @@ pub fn sys_yield() -> SysResult {
     Ok(0)
 }
 
-/// Kill the process
-pub fn sys_kill(pid: usize) -> SysResult {
-    info!("{} killed: {}", thread::current().id(), pid);
-    processor().manager().exit(pid, 0x100);
+/// Send a signal to a process.
+///
+/// Unlike the old behaviour of force-exiting the target regardless of
+/// `signum`, this only marks the signal pending; it is delivered (handler
+/// invoked, or default action applied) the next time the target returns to
+/// user mode, see `check_signals`.
+pub fn sys_kill(pid: usize, signum: usize) -> SysResult {
+    info!("kill: {} -> {}, signal {}", thread::current().id(), pid, signum);
+    if signum >= signal::NSIG {
+        return Err(SysError::EINVAL);
+    }
+    if signum == 0 {
+        // The null signal: POSIX defines `kill(pid, 0)` as an existence/
+        // permission probe that never actually delivers anything.
+        processor().manager().get_status(pid).ok_or(SysError::ESRCH)?;
+        return Ok(0);
+    }
+    processor().manager().send_signal(pid, signum)?;
     if pid == thread::current().id() {
         processor().yield_now();
     }
     Ok(0)
 }
 
+/// Examine and/or change the handler for `signum`.
+///
+/// If `act` is non-null, it replaces the current action; if `oldact` is
+/// non-null, the previous action is written there. `SIGKILL`/`SIGSTOP`
+/// cannot be caught, blocked, or ignored.
+pub fn sys_rt_sigaction(signum: usize, act: *const SigAction, oldact: *mut SigAction) -> SysResult {
+    info!("rt_sigaction: signal {}, act: {:?}, oldact: {:?}", signum, act, oldact);
+    if signum >= signal::NSIG || signum == signal::SIGKILL || signum == signal::SIGSTOP {
+        return Err(SysError::EINVAL);
+    }
+    let proc = process();
+    if !oldact.is_null() {
+        proc.memory_set.check_mut_ptr(oldact)?;
+        let old = proc.signals.action(signum);
+        unsafe { oldact.write(old); }
+    }
+    if !act.is_null() {
+        proc.memory_set.check_ptr(act)?;
+        let new = unsafe { act.read() };
+        drop(proc);
+        process().signals.set_action(signum, new);
+    }
+    Ok(0)
+}
+
+/// How `sys_rt_sigprocmask` should combine `set` with the current mask.
+const SIG_BLOCK: usize = 0;
+const SIG_UNBLOCK: usize = 1;
+const SIG_SETMASK: usize = 2;
+
+/// Fetch and/or update the calling thread's blocked-signal mask.
+pub fn sys_rt_sigprocmask(how: usize, set: *const u64, oldset: *mut u64) -> SysResult {
+    info!("rt_sigprocmask: how: {}, set: {:?}, oldset: {:?}", how, set, oldset);
+    let mut proc = process();
+    if !oldset.is_null() {
+        proc.memory_set.check_mut_ptr(oldset)?;
+        let old = proc.signals.blocked;
+        unsafe { oldset.write(old); }
+    }
+    if !set.is_null() {
+        proc.memory_set.check_ptr(set)?;
+        let set = unsafe { set.read() };
+        let blocked = match how {
+            SIG_BLOCK => proc.signals.blocked | set,
+            SIG_UNBLOCK => proc.signals.blocked & !set,
+            SIG_SETMASK => set,
+            _ => return Err(SysError::EINVAL),
+        };
+        proc.signals.set_blocked(blocked);
+    }
+    Ok(0)
+}
+
+/// Return from a signal handler: restore the `TrapFrame` and blocked mask
+/// that were saved by `check_signals` before the handler was entered.
+pub fn sys_sigreturn(tf: &mut TrapFrame) -> SysResult {
+    info!("sigreturn: {}", thread::current().id());
+    let mut proc = process();
+    let frame = proc.signal_frames.pop().ok_or(SysError::EINVAL)?;
+    proc.signals.blocked = frame.old_mask;
+    let ret = frame.tf.x[10]; // a0: whatever the interrupted syscall was going to return
+    *tf = frame.tf;
+    Ok(ret)
+}
+
+/// Called on the way back to user mode. If a pending, unblocked signal is
+/// deliverable, either invoke its handler (pushing a `SignalFrame` and
+/// redirecting `tf` to the handler with a return trampoline at
+/// `restorer`) or apply the default action.
+pub fn check_signals(tf: &mut TrapFrame) {
+    let mut proc = process();
+    let signum = match proc.signals.take_deliverable() {
+        Some(s) => s,
+        None => return,
+    };
+    let action = proc.signals.action(signum);
+    if action.handler == signal::SIG_IGN {
+        return;
+    }
+    if proc.trace.is_traced() {
+        // Hand the signal to the tracer first; `PTRACE_CONT` with a
+        // non-zero `data` would normally let it inject a different signal,
+        // but forwarding the original one is enough for now.
+        drop(proc);
+        ptrace_stop(TraceStop::Signal(signum));
+        proc = process();
+    }
+    if action.handler == signal::SIG_DFL {
+        drop(proc);
+        match signal::default_action(signum) {
+            SignalDefault::Terminate | SignalDefault::Core => {
+                let pid = thread::current().id();
+                processor().manager().exit(pid, ExitStatus::Signaled {
+                    signal: signum as u8,
+                    core_dump: signal::default_action(signum) == SignalDefault::Core,
+                });
+                processor().yield_now();
+            }
+            SignalDefault::Stop => {
+                processor().manager().stop(thread::current().id());
+                processor().yield_now();
+            }
+            SignalDefault::Continue | SignalDefault::Ignore => {}
+        }
+        return;
+    }
+    // Save the interrupted context and mask, then divert to the handler:
+    // `handler(signum)` with `ra` pointing at the `sys_sigreturn` trampoline.
+    // Pushed rather than stored in a single slot, so a second unblocked
+    // signal arriving before `sys_sigreturn` runs nests instead of
+    // clobbering the first handler's interrupted context.
+    proc.signal_frames.push(SignalFrame { tf: tf.clone(), old_mask: proc.signals.blocked });
+    proc.signals.set_blocked(proc.signals.blocked | action.mask | (1 << signum));
+    let handler = action.handler;
+    let restorer = action.restorer;
+    drop(proc);
+    tf.sepc = handler;
+    tf.x[10] = signum; // a0
+    tf.x[1] = restorer; // ra
+}
+
+/// Stop the current process for the tracer to observe, and block in the
+/// scheduler until the tracer issues `PTRACE_CONT`/`PTRACE_SINGLESTEP`.
+///
+/// Called on syscall entry (from the syscall dispatcher, for a traced
+/// process) and from `check_signals` right before any signal that is not
+/// `SIG_IGN` is handled, whether by a user handler or its default action.
+///
+/// This kernel has no hardware single-step trap and no per-instruction
+/// hook, so `PTRACE_SINGLESTEP` can only offer the granularity these two
+/// call sites provide: once resumed, the tracee actually runs (unlike the
+/// previous implementation, which re-stopped it here without ever
+/// returning to user mode) until its next syscall entry or non-ignored signal.
+/// `trace.single_step` is kept for the tracer side (`resume_tracee`) to
+/// record which kind of resume was requested; it is not consulted here,
+/// since every stop already re-stops on the next of those two events
+/// regardless of mode.
+pub fn ptrace_stop(stop: TraceStop) {
+    let pid = thread::current().id();
+    {
+        let mut proc = process();
+        if !proc.trace.is_traced() {
+            return;
+        }
+        proc.trace.stop = Some(stop);
+    }
+    processor().manager().set_status(pid, Status::Traced);
+    loop {
+        processor().yield_now();
+        let proc = process();
+        if proc.trace.stop.is_none() {
+            return;
+        }
+        drop(proc);
+        thread::yield_now();
+    }
+}
+
+/// Inspect or control a traced (child) process.
+pub fn sys_ptrace(request: usize, pid: usize, addr: usize, data: usize) -> SysResult {
+    info!("ptrace: request: {}, pid: {}, addr: {:#x}, data: {:#x}", request, pid, addr, data);
+    match request {
+        ptrace::PTRACE_TRACEME => {
+            let child = thread::current().id();
+            let parent = processor().manager().get_parent(child);
+            process().trace.tracer = Some(parent);
+            Ok(0)
+        }
+        ptrace::PTRACE_ATTACH => {
+            let tracer = thread::current().id();
+            processor().manager().attach_tracer(pid, tracer)?;
+            // `sys_wait4`'s traced-stop branch is only ever reached via
+            // `get_children(tracer)`, so `attach_tracer` must reparent
+            // `pid` to `tracer` for an attacher that isn't `pid`'s
+            // biological parent to ever observe the trace-stop.
+            debug_assert_eq!(processor().manager().get_parent(pid), tracer);
+            Ok(0)
+        }
+        ptrace::PTRACE_PEEKTEXT | ptrace::PTRACE_PEEKDATA => {
+            let word = processor().manager().read_tracee_word(pid, addr)?;
+            Ok(word)
+        }
+        ptrace::PTRACE_POKETEXT | ptrace::PTRACE_POKEDATA => {
+            processor().manager().write_tracee_word(pid, addr, data)?;
+            Ok(0)
+        }
+        ptrace::PTRACE_GETREGS => {
+            process().memory_set.check_mut_ptr(data as *mut PtraceRegs)?;
+            let regs = processor().manager().get_tracee_regs(pid)?;
+            unsafe { (data as *mut PtraceRegs).write(regs); }
+            Ok(0)
+        }
+        ptrace::PTRACE_SETREGS => {
+            process().memory_set.check_ptr(data as *const PtraceRegs)?;
+            let regs = unsafe { (data as *const PtraceRegs).read() };
+            processor().manager().set_tracee_regs(pid, regs)?;
+            Ok(0)
+        }
+        ptrace::PTRACE_SINGLESTEP => {
+            processor().manager().resume_tracee(pid, true)?;
+            Ok(0)
+        }
+        ptrace::PTRACE_CONT => {
+            processor().manager().resume_tracee(pid, false)?;
+            Ok(0)
+        }
+        ptrace::PTRACE_DETACH => {
+            processor().manager().detach_tracer(pid)?;
+            Ok(0)
+        }
+        _ => Err(SysError::EINVAL),
+    }
+}
+
+/// Read the calling process's limit for `resource` into `*rlim`.
+pub fn sys_getrlimit(resource: usize, rlim: *mut RLimit) -> SysResult {
+    info!("getrlimit: resource: {}, rlim: {:?}", resource, rlim);
+    process().memory_set.check_mut_ptr(rlim)?;
+    let proc = process();
+    let limit = proc.rlimits.get(resource).ok_or(SysError::EINVAL)?;
+    unsafe { rlim.write(limit); }
+    Ok(0)
+}
+
+/// Set the calling process's limit for `resource` from `*rlim`.
+pub fn sys_setrlimit(resource: usize, rlim: *const RLimit) -> SysResult {
+    info!("setrlimit: resource: {}, rlim: {:?}", resource, rlim);
+    process().memory_set.check_ptr(rlim)?;
+    let new = unsafe { rlim.read() };
+    let mut proc = process();
+    // There's no notion of root yet, so nobody is privileged: a caller may
+    // lower its soft limit or raise it up to the hard cap, but can never
+    // raise the hard cap itself. `ResourceLimits::set`'s hard-cap check is the only
+    // enforcement in this path, so it must actually run.
+    proc.rlimits.set(resource, new, false).map_err(|_| SysError::EINVAL)?;
+    Ok(0)
+}
+
+/// `getrlimit`/`setrlimit` combined, for an arbitrary `pid` (0 means self).
+pub fn sys_prlimit64(pid: usize, resource: usize, new_limit: *const RLimit, old_limit: *mut RLimit) -> SysResult {
+    info!("prlimit64: pid: {}, resource: {}, new: {:?}, old: {:?}", pid, resource, new_limit, old_limit);
+    if resource >= RLIMIT_NLIMITS {
+        return Err(SysError::EINVAL);
+    }
+    let pid = if pid == 0 { thread::current().id() } else { pid };
+    if !old_limit.is_null() {
+        process().memory_set.check_mut_ptr(old_limit)?;
+        let old = processor().manager().get_rlimit(pid, resource).ok_or(SysError::ESRCH)?;
+        unsafe { old_limit.write(old); }
+    }
+    if !new_limit.is_null() {
+        process().memory_set.check_ptr(new_limit)?;
+        let new = unsafe { new_limit.read() };
+        // Same "nobody is privileged yet" rule as `sys_setrlimit`.
+        processor().manager().set_rlimit(pid, resource, new, false)
+            .map_err(|_| SysError::EINVAL)?;
+    }
+    Ok(0)
+}
+
+/// Restrict the set of harts `pid` (0 means self) may run on.
+pub fn sys_sched_setaffinity(pid: usize, cpusetsize: usize, mask: *const u64) -> SysResult {
+    info!("sched_setaffinity: pid: {}, cpusetsize: {}, mask: {:?}", pid, cpusetsize, mask);
+    if cpusetsize < core::mem::size_of::<u64>() {
+        return Err(SysError::EINVAL);
+    }
+    process().memory_set.check_ptr(mask)?;
+    let bits = unsafe { mask.read() };
+    let cpu_mask = CpuMask::from_bits(bits);
+    if !cpu_mask.is_valid(processor().manager().online_cpu_mask()) {
+        return Err(SysError::EINVAL);
+    }
+    let pid = if pid == 0 { thread::current().id() } else { pid };
+    processor().manager().set_affinity(pid, cpu_mask);
+    Ok(0)
+}
+
+/// Read back the affinity mask for `pid` (0 means self).
+pub fn sys_sched_getaffinity(pid: usize, cpusetsize: usize, mask: *mut u64) -> SysResult {
+    info!("sched_getaffinity: pid: {}, cpusetsize: {}, mask: {:?}", pid, cpusetsize, mask);
+    if cpusetsize < core::mem::size_of::<u64>() {
+        return Err(SysError::EINVAL);
+    }
+    process().memory_set.check_mut_ptr(mask)?;
+    let pid = if pid == 0 { thread::current().id() } else { pid };
+    let cpu_mask = processor().manager().get_affinity(pid).ok_or(SysError::ESRCH)?;
+    unsafe { mask.write(cpu_mask.bits()); }
+    Ok(0)
+}
+
+/// Return the id of the hart the caller is currently running on.
+pub fn sys_getcpu(cpu: *mut u32, node: *mut u32) -> SysResult {
+    let id = processor().cpu_id() as u32;
+    if !cpu.is_null() {
+        process().memory_set.check_mut_ptr(cpu)?;
+        unsafe { cpu.write(id); }
+    }
+    if !node.is_null() {
+        process().memory_set.check_mut_ptr(node)?;
+        // Single NUMA node.
+        unsafe { node.write(0); }
+    }
+    Ok(0)
+}
+
 /// Get the current process id
 pub fn sys_getpid() -> SysResult {
     Ok(thread::current().id())
@@ -150,6 +573,60 @@ pub fn sys_getppid() -> SysResult {
     Ok(ppid)
 }
 
+/// Stable, `/proc`-style view of a process's scheduling state, read by
+/// `sys_process_info` instead of letting callers poke at `Status` directly.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcState {
+    Running = 0,
+    Sleeping = 1,
+    Zombie = 2,
+    Stopped = 3,
+    Traced = 4,
+}
+
+impl From<&Status> for ProcState {
+    fn from(status: &Status) -> Self {
+        match status {
+            Status::Exited(_) => ProcState::Zombie,
+            Status::Traced => ProcState::Traced,
+            Status::Stopped => ProcState::Stopped,
+            Status::Sleeping => ProcState::Sleeping,
+            _ => ProcState::Running,
+        }
+    }
+}
+
+/// Snapshot of a process returned by `sys_process_info`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct ProcessInfo {
+    pub state: ProcState,
+    pub ppid: usize,
+    pub num_fds: usize,
+    pub cpu_ticks: u64,
+}
+
+/// Query a `/proc`-style status snapshot for `pid` (any child of the
+/// caller, or the caller itself).
+pub fn sys_process_info(pid: usize, buf: *mut ProcessInfo) -> SysResult {
+    info!("process_info: pid: {}, buf: {:?}", pid, buf);
+    process().memory_set.check_mut_ptr(buf)?;
+    let caller = thread::current().id();
+    if pid != caller && !processor().manager().get_children(caller).iter().any(|&p| p == pid) {
+        return Err(SysError::ESRCH);
+    }
+    let status = processor().manager().get_status(pid).ok_or(SysError::ESRCH)?;
+    let info = ProcessInfo {
+        state: ProcState::from(&status),
+        ppid: processor().manager().get_parent(pid),
+        num_fds: processor().manager().get_num_fds(pid),
+        cpu_ticks: processor().manager().get_cpu_ticks(pid),
+    };
+    unsafe { buf.write(info); }
+    Ok(0)
+}
+
 /// Exit the current process
 pub fn sys_exit(exit_code: isize) -> ! {
     let pid = thread::current().id();
@@ -164,7 +641,7 @@ pub fn sys_exit(exit_code: isize) -> ! {
     }
     drop(proc);
 
-    processor().manager().exit(pid, exit_code as usize);
+    processor().manager().exit(pid, ExitStatus::Normal(exit_code as i32));
     processor().yield_now();
     unreachable!();
 }